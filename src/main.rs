@@ -1,14 +1,20 @@
 use anyhow::{Context, Result};
 use clap::Parser;
+use futures_util::future::join_all;
 use futures_util::StreamExt;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use reqwest::{Client, header, redirect};
+use scraper::{Html, Selector};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
 use std::collections::HashSet;
 use std::fs::{self, File, OpenOptions};
-use std::io::Write;
+use std::future::Future;
+use std::io::{Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use tokio::sync::Semaphore;
+use std::pin::Pin;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{Mutex, Semaphore};
 use url::Url;
 
 #[derive(Parser, Debug)]
@@ -51,13 +57,252 @@ struct Args {
 
     #[clap(short = 'd', long)]
     same_domain: bool,
+
+    // Verify integrity after download, e.g. "sha256:<hex>"
+    #[clap(long)]
+    checksum: Option<String>,
+
+    // Look up the expected hash in a sha256sum/md5sum-style list instead of
+    // passing it directly
+    #[clap(long)]
+    checksum_file: Option<PathBuf>,
+
+    // Keep the output file even if checksum verification fails
+    #[clap(long)]
+    keep_corrupt: bool,
+
+    // Proxy URL (http, https, socks5, or socks5h). Falls back to the
+    // HTTPS_PROXY/HTTP_PROXY/ALL_PROXY environment variables
+    #[clap(long)]
+    proxy: Option<String>,
+
+    // Split a single large file into N concurrent range requests
+    #[clap(long, default_value = "1")]
+    segments: usize,
+
+    // Remove stale .partial files older than --partial-max-age before downloading
+    #[clap(long)]
+    clean_partials: bool,
+
+    // Age threshold for --clean-partials, e.g. "7d", "12h", "30m", "45s"
+    #[clap(long, default_value = "7d")]
+    partial_max_age: String,
+}
+
+struct Checksum {
+    algo: String,
+    expected: String,
+}
+
+enum ChecksumHasher {
+    Sha256(Sha256),
+    Sha1(Sha1),
+    Md5(md5::Context),
+}
+
+impl ChecksumHasher {
+    fn new(algo: &str) -> Result<Self> {
+        match algo {
+            "sha256" => Ok(Self::Sha256(Sha256::new())),
+            "sha1" => Ok(Self::Sha1(Sha1::new())),
+            "md5" => Ok(Self::Md5(md5::Context::new())),
+            other => anyhow::bail!("Unsupported checksum algorithm: {}", other),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Sha1(hasher) => hasher.update(data),
+            Self::Md5(hasher) => hasher.consume(data),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Self::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Sha1(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Md5(hasher) => format!("{:x}", hasher.compute()),
+        }
+    }
+}
+
+fn parse_checksum_spec(spec: &str) -> Result<Checksum> {
+    let (algo, hex) = spec
+        .split_once(':')
+        .context("--checksum must be in the form <algo>:<hex>, e.g. sha256:abcd1234")?;
+
+    Ok(Checksum {
+        algo: algo.to_lowercase(),
+        expected: hex.to_lowercase(),
+    })
+}
+
+fn algo_from_hex_len(len: usize) -> Result<&'static str> {
+    match len {
+        64 => Ok("sha256"),
+        40 => Ok("sha1"),
+        32 => Ok("md5"),
+        other => anyhow::bail!("Cannot infer checksum algorithm from a {}-character hash", other),
+    }
+}
+
+// Looks up `filename` in a sha256sum/md5sum-style checksum list ("<hex>
+// <filename>" per line).
+fn find_checksum_in_file(checksum_file: &Path, filename: &str) -> Result<Option<Checksum>> {
+    let content = fs::read_to_string(checksum_file)
+        .with_context(|| format!("Failed to read checksum file: {}", checksum_file.display()))?;
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.splitn(2, char::is_whitespace);
+        let hex = parts.next().unwrap_or("");
+        let name = parts.next().unwrap_or("").trim().trim_start_matches('*');
+
+        if name == filename {
+            return Ok(Some(Checksum {
+                algo: algo_from_hex_len(hex.len())?.to_string(),
+                expected: hex.to_lowercase(),
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+fn resolve_checksum(args: &Args, output_path: &Path) -> Result<Option<Checksum>> {
+    if let Some(spec) = &args.checksum {
+        return parse_checksum_spec(spec).map(Some);
+    }
+
+    if let Some(checksum_file) = &args.checksum_file {
+        let filename = output_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+
+        return find_checksum_in_file(checksum_file, filename);
+    }
+
+    Ok(None)
+}
+
+// Attributes worth following while mirroring a page, and the tag/attr pair
+// that carries each one.
+const LINK_ATTRS: [(&str, &str); 4] = [
+    ("a[href]", "href"),
+    ("link[href]", "href"),
+    ("img[src]", "src"),
+    ("script[src]", "src"),
+];
+
+fn extract_links(page_url: &Url, html: &str) -> Vec<Url> {
+    let document = Html::parse_document(html);
+    let mut links = Vec::new();
+
+    for (selector_str, attr) in LINK_ATTRS {
+        let selector = match Selector::parse(selector_str) {
+            Ok(selector) => selector,
+            Err(_) => continue,
+        };
+
+        for element in document.select(&selector) {
+            if let Some(value) = element.value().attr(attr) {
+                if let Ok(joined) = page_url.join(value) {
+                    links.push(joined);
+                }
+            }
+        }
+    }
+
+    links
+}
+
+// Appends the staging suffix used while a download is in flight. Bytes only
+// ever land on `output_path` once the stream finishes cleanly, so a file at
+// the final path is always complete and a `.partial` next to it is a download
+// that has not been promoted yet (usually one that needs more bytes).
+fn partial_path_for(output_path: &Path) -> PathBuf {
+    let mut name = output_path.as_os_str().to_os_string();
+    name.push(".partial");
+    PathBuf::from(name)
+}
+
+// HEAD the URL to learn Content-Length without fetching the body. Used to
+// check whether a `.partial` is actually already complete.
+async fn probe_content_length(client: &Client, url: &Url) -> Option<u64> {
+    let response = client.head(url.clone()).send().await.ok()?;
+
+    if !response.status().is_success() {
+        return None;
+    }
+
+    // Read the header itself: `content_length()` reports the size of the
+    // (always empty) HEAD body, not the size of the resource.
+    response
+        .headers()
+        .get(header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+fn verify_checksum_of_file(path: &Path, checksum: &Checksum, keep_corrupt: bool) -> Result<()> {
+    let mut hasher = ChecksumHasher::new(&checksum.algo)?;
+    hasher.update(&fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?);
+    let actual = hasher.finalize_hex();
+
+    if actual != checksum.expected {
+        if !keep_corrupt {
+            fs::remove_file(path).ok();
+        }
+
+        anyhow::bail!(
+            "Checksum mismatch for {}: expected {} ({}), got {}",
+            path.display(),
+            checksum.expected,
+            checksum.algo,
+            actual
+        );
+    }
+
+    Ok(())
 }
 
-async fn download_file(client: &Client, url: &Url, output_path: &Path, args: &Args) -> Result<()> {
-    let file_exists = output_path.exists();
+// Whether `download_file` actually pulled bytes off the wire, or left an
+// existing/already-complete file alone. Callers that count "files
+// downloaded" (e.g. the recursive crawl summary) need this distinction —
+// a skip isn't a download.
+enum DownloadOutcome {
+    Skipped,
+    // Carries the body when the response is HTML and we're in recursive
+    // mode, so the caller can extract links from it without paying for a
+    // second request.
+    Downloaded(Option<Vec<u8>>),
+}
+
+// Downloads `url` to `output_path`, staging bytes in a `<output_path>.partial`
+// file and only promoting it to `output_path` once the stream finishes
+// cleanly. `multi_progress`, when given, is used to register this
+// download's progress bar so it renders correctly alongside other
+// concurrently active bars (e.g. during a recursive crawl).
+async fn download_file(
+    client: &Client,
+    url: &Url,
+    output_path: &Path,
+    args: &Args,
+    multi_progress: Option<&MultiProgress>,
+) -> Result<DownloadOutcome> {
+    let partial_path = partial_path_for(output_path);
     let mut downloaded_size = 0;
 
-    if file_exists {
+    if output_path.exists() {
         if args.force {
             if args.verbose {
                 println!(
@@ -65,24 +310,67 @@ async fn download_file(client: &Client, url: &Url, output_path: &Path, args: &Ar
                     output_path.display()
                 );
             }
-        } else if args.continue_download {
-            downloaded_size = std::fs::metadata(output_path)
-                .context("Failed to get file metadata")?
-                .len();
-
-            if args.verbose {
-                println!(
-                    "Resuming download from byte pos {} for {}",
-                    downloaded_size,
-                    output_path.display()
-                );
-            }
         } else {
             if args.verbose {
                 println!("Skipping existing file: {}", output_path.display());
             }
 
-            return Ok(());
+            return Ok(DownloadOutcome::Skipped);
+        }
+    } else if partial_path.exists() {
+        if args.force {
+            fs::remove_file(&partial_path).ok();
+        } else {
+            let partial_size = fs::metadata(&partial_path)
+                .context("Failed to get partial file metadata")?
+                .len();
+
+            // A fully-written .partial (same size the server reports) can be
+            // promoted straight away instead of re-downloaded. Skip this for
+            // recursive crawls, which need the body in hand to find links.
+            if !args.recursive {
+                if let Some(total) = probe_content_length(client, url).await {
+                    if total == partial_size {
+                        fs::rename(&partial_path, output_path).with_context(|| {
+                            format!("Failed to promote {}", partial_path.display())
+                        })?;
+
+                        if let Some(checksum) = resolve_checksum(args, output_path)? {
+                            verify_checksum_of_file(output_path, &checksum, args.keep_corrupt)?;
+                        }
+
+                        if args.verbose {
+                            println!(
+                                "Partial download already complete, promoted: {}",
+                                output_path.display()
+                            );
+                        }
+
+                        return Ok(DownloadOutcome::Skipped);
+                    }
+                }
+            }
+
+            if args.continue_download {
+                downloaded_size = partial_size;
+
+                if args.verbose {
+                    println!(
+                        "Resuming download from byte pos {} for {}",
+                        downloaded_size,
+                        output_path.display()
+                    );
+                }
+            } else {
+                if args.verbose {
+                    println!(
+                        "Partial download exists, use --continue to resume or --force to restart: {}",
+                        partial_path.display()
+                    );
+                }
+
+                return Ok(DownloadOutcome::Skipped);
+            }
         }
     }
 
@@ -128,6 +416,11 @@ async fn download_file(client: &Client, url: &Url, output_path: &Path, args: &Ar
 
     let pb = if total_size > 0 {
         let pb = ProgressBar::new(total_size);
+        let pb = match multi_progress {
+            Some(multi_progress) => multi_progress.add(pb),
+            None => pb,
+        };
+
         pb.set_style(ProgressStyle::default_bar()
             .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta}) {msg}")
             .unwrap()
@@ -148,20 +441,20 @@ async fn download_file(client: &Client, url: &Url, output_path: &Path, args: &Ar
     let mut file = if downloaded_size > 0 {
         OpenOptions::new()
             .append(true)
-            .open(output_path)
+            .open(&partial_path)
             .with_context(|| {
                 format!(
                     "Failed to open file for appending: {}",
-                    output_path.display()
+                    partial_path.display()
                 )
             })?
     } else {
-        if let Some(parent) = output_path.parent() {
+        if let Some(parent) = partial_path.parent() {
             fs::create_dir_all(parent).context("Failed to create parent directories")?;
         }
 
-        File::create(output_path)
-            .with_context(|| format!("Failed to create file: {}", output_path.display()))?
+        File::create(&partial_path)
+            .with_context(|| format!("Failed to create file: {}", partial_path.display()))?
     };
 
     let is_html = response
@@ -171,15 +464,39 @@ async fn download_file(client: &Client, url: &Url, output_path: &Path, args: &Ar
         .map(|s| s.contains("text/html"))
         .unwrap_or(false);
 
+    let checksum = resolve_checksum(args, output_path)?;
+    let mut hasher = checksum
+        .as_ref()
+        .map(|checksum| ChecksumHasher::new(&checksum.algo))
+        .transpose()?;
+
+    if let Some(hasher) = &mut hasher {
+        if downloaded_size > 0 {
+            let existing = fs::read(&partial_path).with_context(|| {
+                format!(
+                    "Failed to read existing partial file for checksum seeding: {}",
+                    partial_path.display()
+                )
+            })?;
+
+            hasher.update(&existing);
+        }
+    }
+
+    let collect_content = is_html && args.recursive;
     let mut stream = response.bytes_stream();
     let mut content = Vec::new();
 
     while let Some(chunk_res) = stream.next().await {
         let chunk = chunk_res.context("Error while downloading file")?;
         file.write_all(&chunk)
-            .with_context(|| format!("Failed to write to file: {}", output_path.display()))?;
+            .with_context(|| format!("Failed to write to file: {}", partial_path.display()))?;
 
-        if is_html && args.recursive {
+        if let Some(hasher) = &mut hasher {
+            hasher.update(&chunk);
+        }
+
+        if collect_content {
             content.extend_from_slice(&chunk);
         }
 
@@ -192,38 +509,355 @@ async fn download_file(client: &Client, url: &Url, output_path: &Path, args: &Ar
         pb.finish_with_message(format!("Downloaded: {}", output_path.display()));
     }
 
+    if let (Some(hasher), Some(checksum)) = (hasher, &checksum) {
+        let actual = hasher.finalize_hex();
+
+        if actual != checksum.expected {
+            drop(file);
+
+            if !args.keep_corrupt {
+                fs::remove_file(&partial_path).ok();
+            }
+
+            anyhow::bail!(
+                "Checksum mismatch for {}: expected {} ({}), got {}",
+                partial_path.display(),
+                checksum.expected,
+                checksum.algo,
+                actual
+            );
+        } else if args.verbose {
+            println!(
+                "Checksum verified ({}) for {}",
+                checksum.algo,
+                output_path.display()
+            );
+        }
+    }
+
+    drop(file);
+    fs::rename(&partial_path, output_path)
+        .with_context(|| format!("Failed to promote {} to {}", partial_path.display(), output_path.display()))?;
+
     if args.verbose {
         println!("Download complete: {}", output_path.display());
     }
 
-    // Return the content if it's HTML and we're doing recursive download
-    Ok(())
+    Ok(DownloadOutcome::Downloaded(if collect_content {
+        Some(content)
+    } else {
+        None
+    }))
+}
+
+fn resolve_proxy_url(args: &Args) -> Option<String> {
+    args.proxy
+        .clone()
+        .or_else(|| std::env::var("HTTPS_PROXY").ok())
+        .or_else(|| std::env::var("HTTP_PROXY").ok())
+        .or_else(|| std::env::var("ALL_PROXY").ok())
+}
+
+// Parses and validates a proxy URL, rewriting plain `socks5://` to
+// `socks5h://` so DNS resolution happens on the proxy side rather than
+// locally (needed when downloading through Tor or other hidden services).
+fn normalize_proxy_url(raw: &str) -> Result<Url> {
+    let mut proxy_url = Url::parse(raw).with_context(|| format!("Invalid --proxy URL: {}", raw))?;
+
+    if proxy_url.scheme() == "socks5" {
+        proxy_url
+            .set_scheme("socks5h")
+            .map_err(|_| anyhow::anyhow!("Failed to normalize socks5:// proxy scheme: {}", raw))?;
+    }
+
+    Ok(proxy_url)
 }
 
-async fn download_recursively(
+// Splits [0, total) into `segments` roughly equal, inclusive byte ranges.
+// A zero-length resource has no bytes to range over, and we never want more
+// segments than bytes (each segment needs at least 1 byte, or `chunk` would
+// be 0 and the end-offset arithmetic below would underflow).
+fn compute_ranges(total: u64, segments: usize) -> Vec<(u64, u64)> {
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let segments = (segments as u64).clamp(1, total);
+    let chunk = total / segments;
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    for i in 0..segments {
+        let end = if i == segments - 1 {
+            total - 1
+        } else {
+            start + chunk - 1
+        };
+
+        ranges.push((start, end));
+        start = end + 1;
+    }
+
+    ranges
+}
+
+// Fetches a single byte range and writes it at the matching offset in the
+// (already preallocated) partial file.
+async fn download_segment(
     client: &Client,
-    url: Url,
-    base_dir: &Path,
-    depth: usize,
-    max_depth: usize,
-    visited: &mut HashSet<String>,
-    _multi_progress: Arc<MultiProgress>,
-    semaphore: Arc<Semaphore>,
-    args: &Args,
+    url: &Url,
+    partial_path: &Path,
+    start: u64,
+    end: u64,
+    pb: &ProgressBar,
 ) -> Result<()> {
-    let url_str = url.to_string();
+    let response = client
+        .get(url.clone())
+        .header(header::RANGE, format!("bytes={}-{}", start, end))
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch bytes {}-{} of {}", start, end, url))?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "Server returned error status {} for bytes {}-{} of {}",
+            response.status(),
+            start,
+            end,
+            url
+        );
+    }
+
+    // Each segment opens its own handle so concurrent writes to
+    // non-overlapping regions don't need to share (and contend on) one
+    // cursor.
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(partial_path)
+        .with_context(|| format!("Failed to open file for writing: {}", partial_path.display()))?;
+
+    file.seek(SeekFrom::Start(start))
+        .context("Failed to seek to segment offset")?;
+
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk_res) = stream.next().await {
+        let chunk = chunk_res
+            .with_context(|| format!("Error while downloading bytes {}-{} of {}", start, end, url))?;
+
+        file.write_all(&chunk)
+            .with_context(|| format!("Failed to write to file: {}", partial_path.display()))?;
+
+        pb.inc(chunk.len() as u64);
+    }
+
+    Ok(())
+}
+
+// Downloads a single large file as `args.segments` concurrent range
+// requests. Falls back to the regular single-stream `download_file` when the
+// server doesn't advertise range support.
+async fn download_segmented(client: &Client, url: &Url, output_path: &Path, args: &Args) -> Result<()> {
+    if output_path.exists() && !args.force {
+        if args.verbose {
+            println!("Skipping existing file: {}", output_path.display());
+        }
 
-    if visited.contains(&url_str) {
         return Ok(());
     }
 
-    // Mark as visited
-    visited.insert(url_str);
+    let probe = client
+        .get(url.clone())
+        .header(header::RANGE, "bytes=0-0")
+        .send()
+        .await
+        .context("Failed to send range probe request")?;
+
+    let supports_ranges = probe.status().as_u16() == 206;
+    let total_size = if supports_ranges {
+        probe
+            .headers()
+            .get(header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.rsplit('/').next())
+            .and_then(|v| v.parse::<u64>().ok())
+    } else {
+        probe.content_length()
+    };
+
+    drop(probe);
+
+    let Some(total_size) = total_size.filter(|_| supports_ranges) else {
+        if args.verbose {
+            println!(
+                "Server doesn't support range requests, falling back to a single stream for {}",
+                url
+            );
+        }
 
-    if depth > max_depth {
+        download_file(client, url, output_path, args, None).await?;
         return Ok(());
+    };
+
+    let partial_path = partial_path_for(output_path);
+
+    if let Some(parent) = partial_path.parent() {
+        fs::create_dir_all(parent).context("Failed to create parent directories")?;
+    }
+
+    {
+        let file = File::create(&partial_path)
+            .with_context(|| format!("Failed to create file: {}", partial_path.display()))?;
+
+        file.set_len(total_size)
+            .context("Failed to preallocate output file")?;
+    }
+
+    let multi_progress = MultiProgress::new();
+    let pb = multi_progress.add(ProgressBar::new(total_size));
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta}) {msg}")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+    pb.set_message(format!("{}", output_path.display()));
+
+    let semaphore = Semaphore::new(args.max_concurrent);
+    let ranges = compute_ranges(total_size, args.segments);
+
+    let downloads = ranges.into_iter().map(|(start, end)| {
+        let semaphore = &semaphore;
+        let pb = &pb;
+        let partial_path = &partial_path;
+
+        async move {
+            let _permit = semaphore.acquire().await?;
+            download_segment(client, url, partial_path, start, end, pb).await
+        }
+    });
+
+    for result in join_all(downloads).await {
+        result?;
+    }
+
+    pb.finish_with_message(format!("Downloaded: {}", output_path.display()));
+
+    if let Some(checksum) = resolve_checksum(args, output_path)? {
+        verify_checksum_of_file(&partial_path, &checksum, args.keep_corrupt)?;
+    }
+
+    fs::rename(&partial_path, output_path).with_context(|| {
+        format!(
+            "Failed to promote {} to {}",
+            partial_path.display(),
+            output_path.display()
+        )
+    })?;
+
+    if args.verbose {
+        println!("Download complete: {}", output_path.display());
+    }
+
+    Ok(())
+}
+
+// Parses an age threshold like "7d", "12h", "30m", "45s" (a bare number is
+// taken as seconds).
+fn parse_duration_spec(spec: &str) -> Result<Duration> {
+    let spec = spec.trim();
+
+    if let Ok(secs) = spec.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let split_at = spec
+        .len()
+        .checked_sub(1)
+        .with_context(|| format!("Invalid duration: {}", spec))?;
+    let (amount, unit) = spec.split_at(split_at);
+
+    let multiplier: u64 = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => anyhow::bail!("Invalid duration '{}', expected e.g. 7d, 12h, 30m, 45s", spec),
+    };
+
+    let amount: u64 = amount
+        .parse()
+        .with_context(|| format!("Invalid duration value: {}", spec))?;
+
+    Ok(Duration::from_secs(amount * multiplier))
+}
+
+// Recursively removes `*.partial` files under `dir` whose mtime is older
+// than `max_age`. Aborted resumable downloads otherwise accumulate forever,
+// since nothing else ever revisits or reclaims them.
+fn clean_stale_partials(dir: &Path, max_age: Duration, verbose: bool) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    let now = SystemTime::now();
+    let mut removed = 0usize;
+    let mut freed_bytes = 0u64;
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(current) = stack.pop() {
+        let entries = fs::read_dir(&current)
+            .with_context(|| format!("Failed to read directory: {}", current.display()))?;
+
+        for entry in entries {
+            let entry = entry.context("Failed to read directory entry")?;
+            let path = entry.path();
+            let metadata = entry
+                .metadata()
+                .with_context(|| format!("Failed to stat {}", path.display()))?;
+
+            if metadata.is_dir() {
+                stack.push(path);
+                continue;
+            }
+
+            if path.extension().and_then(|ext| ext.to_str()) != Some("partial") {
+                continue;
+            }
+
+            let age = now
+                .duration_since(metadata.modified().with_context(|| {
+                    format!("Failed to get modified time for {}", path.display())
+                })?)
+                .unwrap_or_default();
+
+            if age > max_age {
+                let size = metadata.len();
+
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove stale partial: {}", path.display()))?;
+
+                removed += 1;
+                freed_bytes += size;
+
+                if verbose {
+                    println!("Removed stale partial download: {}", path.display());
+                }
+            }
+        }
     }
 
+    if verbose && removed > 0 {
+        println!(
+            "Cleaned {} stale .partial file(s), freed {} bytes",
+            removed, freed_bytes
+        );
+    }
+
+    Ok(())
+}
+
+fn output_path_for(base_dir: &Path, url: &Url) -> PathBuf {
     let path = url.path();
     let path = if path.ends_with("/") || path.is_empty() {
         "index.html"
@@ -231,27 +865,112 @@ async fn download_recursively(
         path.trim_start_matches("/")
     };
 
-    let output_path = base_dir.join(path);
+    base_dir.join(path)
+}
 
-    // Using semaphore to limit concurrent downloads
-    let _permit = semaphore.acquire().await?;
-    download_file(client, &url, &output_path, args).await?;
+// Shared, read-only state for a recursive crawl. Bundled into one struct so
+// `download_recursively` doesn't have to carry each piece through its own
+// parameter (and every recursive call) individually.
+struct CrawlContext<'a> {
+    client: &'a Client,
+    base_dir: &'a Path,
+    max_depth: usize,
+    visited: &'a Mutex<HashSet<String>>,
+    multi_progress: &'a MultiProgress,
+    semaphore: &'a Semaphore,
+    args: &'a Args,
+}
 
-    let response = client
-        .get(url.clone())
-        .send()
-        .await
-        .context("Failed to send request")?;
+// Crawls `url` and everything it links to (within `max_depth` and
+// `same_domain`), downloading each page and recursing into discovered links
+// concurrently. Boxed because the function recurses through an `async`
+// block, which `async fn` can't do on its own. Returns the number of files
+// downloaded.
+fn download_recursively<'a>(
+    ctx: &'a CrawlContext<'a>,
+    url: Url,
+    depth: usize,
+) -> Pin<Box<dyn Future<Output = Result<usize>> + Send + 'a>> {
+    Box::pin(async move {
+        let url_str = url.to_string();
 
-    if !response.status().is_success() {
-        if args.verbose {
-            println!("Skipping {} due to status code {}", url, response.status());
+        {
+            let mut visited = ctx.visited.lock().await;
+
+            if visited.contains(&url_str) {
+                return Ok(0);
+            }
+
+            visited.insert(url_str);
         }
 
-        return Ok(());
-    }
+        if depth > ctx.max_depth {
+            return Ok(0);
+        }
 
-    Ok(())
+        let output_path = output_path_for(ctx.base_dir, &url);
+
+        // Using semaphore to limit concurrent downloads
+        let outcome = {
+            let _permit = ctx.semaphore.acquire().await?;
+
+            match download_file(
+                ctx.client,
+                &url,
+                &output_path,
+                ctx.args,
+                Some(ctx.multi_progress),
+            )
+            .await
+            {
+                Ok(outcome) => outcome,
+                Err(err) => {
+                    if ctx.args.verbose {
+                        println!("Skipping {} due to error: {}", url, err);
+                    }
+
+                    return Ok(0);
+                }
+            }
+        };
+
+        let (mut downloaded, content) = match outcome {
+            DownloadOutcome::Skipped => (0, None),
+            DownloadOutcome::Downloaded(content) => (1, content),
+        };
+
+        let Some(content) = content else {
+            return Ok(downloaded);
+        };
+
+        if depth >= ctx.max_depth {
+            return Ok(downloaded);
+        }
+
+        let html = String::from_utf8_lossy(&content);
+        let links: Vec<Url> = extract_links(&url, &html)
+            .into_iter()
+            .filter(|link| matches!(link.scheme(), "http" | "https"))
+            .filter(|link| !ctx.args.same_domain || link.host_str() == url.host_str())
+            .collect();
+
+        let children = links
+            .into_iter()
+            .map(|link| download_recursively(ctx, link, depth + 1));
+
+        for result in join_all(children).await {
+            match result {
+                Ok(count) => downloaded += count,
+                Err(err) => {
+                    if ctx.args.verbose {
+                        println!("Skipping a linked page due to error: {}", err);
+                    }
+                }
+            }
+        }
+
+        Ok(downloaded)
+    })
 }
 
 #[tokio::main]
@@ -272,14 +991,7 @@ async fn main() -> Result<()> {
     };
 
     let output_path = if args.recursive {
-        let path = url.path();
-        let path = if path.ends_with("/") || path.is_empty() {
-            "index.html"
-        } else {
-            path.trim_start_matches("/")
-        };
-
-        base_dir.join(path)
+        output_path_for(&base_dir, &url)
     } else {
         match &args.output {
             Some(path) => path.clone(),
@@ -300,6 +1012,26 @@ async fn main() -> Result<()> {
         }
     }
 
+    if args.clean_partials {
+        let max_age = parse_duration_spec(&args.partial_max_age)?;
+
+        // For recursive crawls, sweep the whole domain directory. Otherwise
+        // scope the sweep to the output file's own directory (falling back
+        // to "." when the output path has no directory component) rather
+        // than always sweeping cwd, so e.g. `-o /data/movies/file.mp4` only
+        // touches `/data/movies/`.
+        let sweep_dir = if args.recursive {
+            base_dir.as_path()
+        } else {
+            output_path
+                .parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."))
+        };
+
+        clean_stale_partials(sweep_dir, max_age, args.verbose)?;
+    }
+
     if args.verbose {
         println!("Downloading {} to {}", args.url, output_path.display());
     }
@@ -310,37 +1042,72 @@ async fn main() -> Result<()> {
         redirect::Policy::limited(args.max_redirects)
     };
 
-    let client = Client::builder()
-        .redirect(redirect_policy)
+    let mut client_builder = Client::builder().redirect(redirect_policy);
+
+    if let Some(proxy_raw) = resolve_proxy_url(&args) {
+        let proxy_url = normalize_proxy_url(&proxy_raw)?;
+
+        if args.verbose {
+            println!("Using proxy: {}", proxy_url);
+        }
+
+        client_builder = client_builder
+            .proxy(reqwest::Proxy::all(proxy_url).context("Failed to configure proxy")?);
+    }
+
+    let client = client_builder
         .build()
         .context("Failed to build HTTP client")?;
 
     if args.recursive {
         // For recursive downloads, use a different approach
-        let multi_progress = Arc::new(MultiProgress::new());
-        let semaphore = Arc::new(Semaphore::new(args.max_concurrent));
-        let mut visited = HashSet::new();
-
-        download_recursively(
-            &client,
-            url,
-            &base_dir,
-            0,
-            args.max_depth,
-            &mut visited,
-            Arc::clone(&multi_progress),
-            Arc::clone(&semaphore),
-            &args,
-        )
-        .await?;
+        let multi_progress = MultiProgress::new();
+        let semaphore = Semaphore::new(args.max_concurrent);
+        let visited = Mutex::new(HashSet::new());
+
+        let ctx = CrawlContext {
+            client: &client,
+            base_dir: &base_dir,
+            max_depth: args.max_depth,
+            visited: &visited,
+            multi_progress: &multi_progress,
+            semaphore: &semaphore,
+            args: &args,
+        };
+
+        let downloaded = download_recursively(&ctx, url, 0).await?;
 
         println!(
             "Recursive download complete! Downloaded {} files.",
-            visited.len()
+            downloaded
         );
+    } else if args.segments > 1 {
+        download_segmented(&client, &url, &output_path, &args).await?;
     } else {
-        download_file(&client, &url, &output_path, &args).await?;
+        download_file(&client, &url, &output_path, &args, None).await?;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod compute_ranges_tests {
+    use super::*;
+
+    #[test]
+    fn zero_total_has_no_ranges() {
+        assert_eq!(compute_ranges(0, 4), Vec::new());
+    }
+
+    #[test]
+    fn more_segments_than_bytes_clamps_to_one_range_per_byte() {
+        // 3 bytes can't be split into 5 segments, so we fall back to one
+        // segment per byte instead of underflowing on a zero-sized chunk.
+        assert_eq!(compute_ranges(3, 5), vec![(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn even_split_covers_the_whole_range() {
+        assert_eq!(compute_ranges(10, 2), vec![(0, 4), (5, 9)]);
+    }
+}